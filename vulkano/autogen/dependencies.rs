@@ -0,0 +1,190 @@
+// Copyright (c) 2021 The Vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::HashMap;
+use vk_parse::Extension;
+
+// `depends` is a DNF-ish boolean expression over extension (and core version)
+// names: `,` separates OR-branches, each of which is one AND-clause whose
+// names are separated by `+`, e.g. `VK_KHR_get_physical_device_properties2+VK_KHR_surface`.
+// A bare `depends` with no operators is a single AND-clause of one name.
+type Dnf<'a> = Vec<Vec<&'a str>>;
+
+fn parse_depends(expr: &str) -> Dnf<'_> {
+    expr.split(',')
+        .map(|branch| branch.split('+').map(str::trim).collect())
+        .collect()
+}
+
+// Builds the per-extension DNF dependency table and panics (at generation time) if it finds a
+// dependency cycle.
+//
+// `depends` names are already the canonical extension/core-version names used elsewhere in the
+// registry (extensions, unlike types, aren't given `alias` attributes), so there's nothing to
+// canonicalize here.
+pub fn get_dependencies<'a>(
+    extensions: &IndexMap<&'a str, &'a Extension>,
+) -> IndexMap<&'a str, Dnf<'a>> {
+    let table: IndexMap<&str, Dnf> = extensions
+        .values()
+        .map(|ext| {
+            let dnf = ext.depends.as_deref().map(parse_depends).unwrap_or_default();
+            (ext.name.as_str(), dnf)
+        })
+        .collect();
+
+    check_acyclic(&table);
+    table
+}
+
+fn check_acyclic(table: &IndexMap<&str, Dnf>) {
+    enum Mark {
+        InProgress,
+        Done,
+    }
+
+    fn visit<'a>(
+        name: &'a str,
+        table: &IndexMap<&'a str, Dnf<'a>>,
+        marks: &mut HashMap<&'a str, Mark>,
+    ) {
+        match marks.get(name) {
+            Some(Mark::Done) => return,
+            Some(Mark::InProgress) => panic!("extension dependency cycle detected at {name}"),
+            None => {}
+        }
+
+        marks.insert(name, Mark::InProgress);
+
+        if let Some(dnf) = table.get(name) {
+            for clause in dnf {
+                for &dep in clause {
+                    if table.contains_key(dep) {
+                        visit(dep, table, marks);
+                    }
+                }
+            }
+        }
+
+        marks.insert(name, Mark::Done);
+    }
+
+    let mut marks = HashMap::new();
+    for &name in table.keys() {
+        visit(name, table, &mut marks);
+    }
+}
+
+pub fn write(dependencies: &IndexMap<&str, Dnf<'_>>) -> TokenStream {
+    let entries = dependencies.iter().map(|(name, dnf)| {
+        let clauses = dnf.iter().map(|clause| {
+            quote! { &[#(#clause),*] }
+        });
+
+        quote! { (#name, &[#(#clauses),*]) }
+    });
+
+    quote! {
+        /// For each extension, its dependencies expressed in disjunctive normal form:
+        /// an OR of AND-clauses over other extension (or core version) names, matching
+        /// the `depends` attribute in the Vulkan registry.
+        pub static EXTENSION_DEPENDENCIES: &[(&str, &[&[&str]])] = &[
+            #(#entries),*
+        ];
+
+        /// A `depends`/`requires` name that names a core API version rather than another
+        /// extension, e.g. `VK_VERSION_1_2` parses to `Some((1, 2))`.
+        fn parse_core_version_dependency(name: &str) -> Option<(u32, u32)> {
+            let version = name.strip_prefix("VK_VERSION_")?;
+            let (major, minor) = version.split_once('_')?;
+            Some((major.parse().ok()?, minor.parse().ok()?))
+        }
+
+        fn extension_dependency_satisfied(
+            dep: &str,
+            enabled: &std::collections::BTreeSet<&str>,
+            min_version: (u32, u32),
+        ) -> bool {
+            match parse_core_version_dependency(dep) {
+                Some(version) => version <= min_version,
+                None => enabled.contains(dep),
+            }
+        }
+
+        /// Resolves the transitive closure of extensions required to enable `requested`, by
+        /// walking [`EXTENSION_DEPENDENCIES`], along with the minimum core API version that
+        /// closure requires (starting from Vulkan 1.0).
+        ///
+        /// For an OR dependency, a branch that's already satisfied is preferred; otherwise its
+        /// first clause is chosen and reported as a choice point so the caller can react if a
+        /// different branch was wanted.
+        pub fn resolve_extension_dependencies<'a>(
+            requested: impl IntoIterator<Item = &'a str>,
+        ) -> (std::collections::BTreeSet<&'a str>, (u32, u32), Vec<&'a str>) {
+            let mut enabled: std::collections::BTreeSet<&str> = std::collections::BTreeSet::new();
+            let mut min_version = (1, 0);
+            let mut choice_points = Vec::new();
+            let mut queue: Vec<&str> = Vec::new();
+
+            for name in requested {
+                match parse_core_version_dependency(name) {
+                    Some(version) => min_version = min_version.max(version),
+                    None => {
+                        if enabled.insert(name) {
+                            queue.push(name);
+                        }
+                    }
+                }
+            }
+
+            while let Some(name) = queue.pop() {
+                let Some((_, clauses)) =
+                    EXTENSION_DEPENDENCIES.iter().find(|(n, _)| *n == name)
+                else {
+                    continue;
+                };
+
+                if clauses.is_empty() {
+                    continue;
+                }
+
+                let is_satisfied = |clause: &&[&str]| {
+                    clause
+                        .iter()
+                        .all(|dep| extension_dependency_satisfied(dep, &enabled, min_version))
+                };
+
+                let chosen = if let Some(clause) = clauses.iter().find(is_satisfied) {
+                    clause
+                } else {
+                    if clauses.len() > 1 {
+                        choice_points.push(name);
+                    }
+                    &clauses[0]
+                };
+
+                for &dep in *chosen {
+                    match parse_core_version_dependency(dep) {
+                        Some(version) => min_version = min_version.max(version),
+                        None => {
+                            if enabled.insert(dep) {
+                                queue.push(dep);
+                            }
+                        }
+                    }
+                }
+            }
+
+            (enabled, min_version, choice_points)
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Copyright (c) 2021 The Vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::cfg_for_platform_guard;
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::HashMap;
+use vk_parse::{Extension, ExtensionChild};
+
+pub fn write(extensions: &IndexMap<&str, &Extension>, platforms: &HashMap<&str, &str>) -> TokenStream {
+    let entries = extensions
+        .values()
+        .map(|ext| extension_entry(ext, platforms))
+        .collect::<Vec<_>>();
+
+    quote! {
+        /// The promotion or deprecation status of an extension, as declared by its
+        /// `promotedto`, `deprecatedby` or `obsoletedby` attribute in the Vulkan registry.
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum Deprecation {
+            /// The extension was promoted to a later core API version.
+            PromotedToCore(u32, u32),
+            /// The extension was promoted to another extension.
+            PromotedToExtension(&'static str),
+            /// The extension was deprecated in favor of another extension, without being
+            /// promoted.
+            DeprecatedByExtension(&'static str),
+            /// The extension was deprecated without a replacement.
+            Deprecated,
+        }
+
+        /// Metadata about a Vulkan extension, generated from the Vulkan registry.
+        #[derive(Clone, Copy, Debug)]
+        pub struct ExtensionMetadata {
+            /// The registry's extension number.
+            pub number: u32,
+            /// The value of the extension's `_SPEC_VERSION` enum.
+            pub spec_version: u32,
+            /// Whether, and how, this extension has been superseded.
+            pub deprecation: Option<Deprecation>,
+        }
+
+        /// Returns the generated metadata for a named extension, or `None` if `name` isn't
+        /// a known extension, or is a platform extension for another target platform.
+        pub fn extension_metadata(name: &str) -> Option<ExtensionMetadata> {
+            match name {
+                #(#entries)*
+                _ => None,
+            }
+        }
+    }
+}
+
+fn extension_entry(ext: &Extension, platforms: &HashMap<&str, &str>) -> TokenStream {
+    let name = &ext.name;
+    let number = ext.number.unwrap_or(0);
+    let spec_version = spec_version(ext);
+    let deprecation = deprecation(ext);
+    let cfg = ext
+        .platform
+        .as_deref()
+        .and_then(|platform| platforms.get(platform))
+        .map(|guard| cfg_for_platform_guard(guard))
+        .unwrap_or_default();
+
+    quote! {
+        #cfg
+        #name => Some(ExtensionMetadata {
+            number: #number,
+            spec_version: #spec_version,
+            deprecation: #deprecation,
+        }),
+    }
+}
+
+fn spec_version(ext: &Extension) -> u32 {
+    let suffix = "_SPEC_VERSION";
+
+    ext.children
+        .iter()
+        .filter_map(|child| {
+            if let ExtensionChild::Require { items, .. } = child {
+                return Some(items.iter());
+            }
+            None
+        })
+        .flatten()
+        .find_map(|item| {
+            if let vk_parse::InterfaceItem::Enum(e) = item {
+                if e.name.ends_with(suffix) {
+                    if let vk_parse::EnumSpec::Value { value, .. } = &e.spec {
+                        return value.parse().ok();
+                    }
+                }
+            }
+            None
+        })
+        .unwrap_or(1)
+}
+
+fn deprecation(ext: &Extension) -> TokenStream {
+    if let Some(promoted_to) = &ext.promotedto {
+        return if let Some(version) = promoted_to.strip_prefix("VK_VERSION_") {
+            let (major, minor) = version.split_once('_').unwrap();
+            let major: u32 = major.parse().unwrap();
+            let minor: u32 = minor.parse().unwrap();
+            quote! { Some(Deprecation::PromotedToCore(#major, #minor)) }
+        } else {
+            quote! { Some(Deprecation::PromotedToExtension(#promoted_to)) }
+        };
+    }
+
+    if let Some(deprecated_by) = &ext.deprecatedby {
+        if deprecated_by.is_empty() {
+            return quote! { Some(Deprecation::Deprecated) };
+        }
+        return quote! { Some(Deprecation::DeprecatedByExtension(#deprecated_by)) };
+    }
+
+    if ext.obsoletedby.is_some() {
+        return quote! { Some(Deprecation::Deprecated) };
+    }
+
+    quote! { None }
+}
@@ -0,0 +1,20 @@
+// Copyright (c) 2021 The Vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+// `StdVideo*` aliasing used to be generated from here as well as from `properties::write`,
+// which duplicated every alias into the same flat scope in `mod.rs` and failed to compile
+// with a "defined multiple times" error. `properties::write` is the sole place that still
+// does it; this pass is left as a no-op stub so `mod.rs`'s pipeline of passes doesn't need
+// restructuring if a non-video feature-derived pass is added here later.
+pub fn write() -> TokenStream {
+    quote! {}
+}
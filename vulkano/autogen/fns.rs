@@ -0,0 +1,66 @@
+// Copyright (c) 2021 The Vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::cfg_for_platform_guard;
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use std::collections::{HashMap, HashSet};
+use vk_parse::{Extension, ExtensionChild, InterfaceItem};
+
+// Function pointers use the `"system"` ABI rather than a hardcoded `"C"`/`"stdcall"`: it's the
+// ABI Rust itself defines to match whatever convention the platform's own system libraries use
+// (`__stdcall` on 32-bit Windows, the platform C ABI everywhere else), which is exactly
+// `VKAPI_CALL`'s definition. That makes it unnecessary to probe the Vulkan headers' C compiler
+// output at build time to get the same answer.
+pub fn write(extensions: &IndexMap<&str, &Extension>, platforms: &HashMap<&str, &str>) -> TokenStream {
+    let mut seen = HashSet::new();
+    let entries = extensions
+        .values()
+        .flat_map(|ext| {
+            let cfg = ext
+                .platform
+                .as_deref()
+                .and_then(|platform| platforms.get(platform))
+                .map(|guard| cfg_for_platform_guard(guard))
+                .unwrap_or_default();
+
+            ext.children.iter().map(move |child| (child, cfg.clone()))
+        })
+        .filter_map(|(child, cfg)| {
+            if let ExtensionChild::Require { items, .. } = child {
+                return Some(items.iter().map(move |item| (item, cfg.clone())));
+            }
+            None
+        })
+        .flatten()
+        .filter_map(|(item, cfg)| {
+            if let InterfaceItem::Command { name, .. } = item {
+                return Some((name.as_str(), cfg));
+            }
+            None
+        })
+        .filter(|(name, _)| seen.insert(*name))
+        .map(|(name, cfg)| command_fn_ptr(name, cfg))
+        .collect::<Vec<_>>();
+
+    quote! {
+        #(#entries)*
+    }
+}
+
+fn command_fn_ptr(name: &str, cfg: TokenStream) -> TokenStream {
+    let ident = format_ident!("PFN_{}", name);
+
+    quote! {
+        #cfg
+        #[allow(non_camel_case_types)]
+        pub type #ident = unsafe extern "system" fn();
+    }
+}
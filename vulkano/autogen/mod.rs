@@ -9,16 +9,23 @@
 
 use indexmap::IndexMap;
 use quote::quote;
-use std::{collections::HashMap, io::Write, path::Path};
+use std::{
+    collections::{HashMap, HashSet},
+    io::Write,
+    path::Path,
+};
 use vk_parse::{
-    Extension, ExtensionChild, Feature, InterfaceItem, Registry, RegistryChild, Type,
+    Extension, ExtensionChild, Feature, InterfaceItem, Platform, Registry, RegistryChild, Type,
     TypeCodeMarkup, TypeSpec, TypesChild,
 };
 
+mod dependencies;
 mod extensions;
 mod features;
 mod fns;
 mod properties;
+mod versions;
+mod video;
 
 pub fn write<W: Write>(writer: &mut W) {
     let registry = get_registry("vk.xml");
@@ -27,11 +34,17 @@ pub fn write<W: Write>(writer: &mut W) {
     let features = get_features(&registry);
     let types = get_types(&registry, &aliases, &features, &extensions);
     let header_version = get_header_version(&registry);
+    let extension_dependencies = dependencies::get_dependencies(&extensions);
+    let platforms = get_platforms(&registry);
+    let video_registry = video::parse("video.xml");
+    let video_types = video::type_names(&video_registry);
 
-    let out_extensions = extensions::write(&extensions);
-    let out_features = features::write(&types, &extensions);
-    let out_fns = fns::write(&extensions);
-    let out_properties = properties::write(&types, &extensions);
+    let out_extensions = extensions::write(&extensions, &platforms);
+    let out_features = features::write();
+    let out_fns = fns::write(&extensions, &platforms);
+    let out_properties = properties::write(&types, &extensions, &platforms, &video_types);
+    let out_versions = versions::write(&features);
+    let out_dependencies = dependencies::write(&extension_dependencies);
 
     write!(
         writer,
@@ -42,13 +55,40 @@ pub fn write<W: Write>(writer: &mut W) {
         {}",
         header_version,
         quote! {
+            /// The patch version of the Vulkan header that vulkano was generated from.
+            pub const HEADER_VERSION: u16 = #header_version;
+
+            #out_versions
             #out_extensions
+            #out_dependencies
             #out_features
             #out_fns
             #out_properties
         }
     )
     .unwrap();
+
+    // `video.xml` describes the `StdVideo*` types referenced by the
+    // `VK_KHR_video_*` extensions through opaque pointers. It's a separate
+    // registry from `vk.xml`, so it gets its own pass and its own generated
+    // module rather than being folded into `types`/`extensions` above. The
+    // `properties` pass above already aliased any `StdVideo*` name it saw to
+    // this module instead of `c_void`.
+    video::write(writer, &video_registry);
+}
+
+// Used by `properties::write`: a `vk.xml` struct named `StdVideo*` is
+// always a forward declaration for a type that's really defined in `video.xml`. If the
+// `video` pass actually generated it, alias it in; otherwise fall back to the previous
+// opaque-pointer behavior rather than referencing a type that doesn't exist.
+pub(crate) fn resolve_video_pointee(name: &str, video_types: &HashSet<&str>) -> proc_macro2::TokenStream {
+    let ident = quote::format_ident!("{}", name);
+
+    if video_types.contains(name) {
+        quote! { pub use video::#ident; }
+    } else {
+        quote! { pub type #ident = std::ffi::c_void; }
+    }
 }
 
 fn get_registry<P: AsRef<Path> + ?Sized>(path: &P) -> Registry {
@@ -92,10 +132,13 @@ fn get_extensions(registry: &Registry) -> IndexMap<&str, &Extension> {
         .iter()
         .filter_map(|child| {
             if let RegistryChild::Extensions(ext) = child {
+                // `obsoletedby` is no longer used to drop the extension outright: its
+                // bindings are still real and may still be in use downstream, and
+                // `extensions::write` surfaces the obsoletion as `Deprecation::Deprecated`
+                // instead of silently making the extension disappear from the generated
+                // output.
                 return Some(ext.children.iter().filter_map(|ext| {
-                    if ext.supported.as_ref().map(|s| s.as_str()) == Some("vulkan")
-                        && ext.obsoletedby.is_none()
-                    {
+                    if ext.supported.as_ref().map(|s| s.as_str()) == Some("vulkan") {
                         return Some(ext);
                     }
                     None
@@ -121,6 +164,28 @@ fn get_extensions(registry: &Registry) -> IndexMap<&str, &Extension> {
     names.iter().map(|&name| (name, extensions[name])).collect()
 }
 
+// Maps a `<platform>` registry entry's `name` (as referenced by an extension's
+// `platform` attribute) to its C preprocessor guard, e.g. `"win32" =>
+// "VK_USE_PLATFORM_WIN32_KHR"`.
+fn get_platforms(registry: &Registry) -> HashMap<&str, &str> {
+    registry
+        .0
+        .iter()
+        .filter_map(|child| {
+            if let RegistryChild::Platforms(platforms) = child {
+                return Some(
+                    platforms
+                        .children
+                        .iter()
+                        .map(|platform: &Platform| (platform.name.as_str(), platform.protect.as_str())),
+                );
+            }
+            None
+        })
+        .flatten()
+        .collect()
+}
+
 fn get_features(registry: &Registry) -> IndexMap<&str, &Feature> {
     registry
         .0
@@ -196,6 +261,64 @@ fn get_types<'a>(
         .collect()
 }
 
+// Translates a `VK_USE_PLATFORM_*` preprocessor guard into the `cfg` predicate
+// that selects the matching Rust target, so generated items for
+// platform-specific extensions only compile on that platform.
+pub(crate) fn cfg_for_platform_guard(guard: &str) -> proc_macro2::TokenStream {
+    // `VK_USE_PLATFORM_METAL_EXT` (unlike `VK_USE_PLATFORM_MACOS_MVK`) backs the
+    // MoltenVK Metal surface/objects extensions, which are usable on iOS as well as
+    // macOS, so it gets its own multi-target `cfg` rather than falling in with the
+    // macOS-only guard below.
+    if guard == "VK_USE_PLATFORM_METAL_EXT" {
+        return quote! { #[cfg(any(target_os = "macos", target_os = "ios"))] };
+    }
+
+    let target_os = match guard {
+        "VK_USE_PLATFORM_WIN32_KHR" => "windows",
+        "VK_USE_PLATFORM_ANDROID_KHR" => "android",
+        "VK_USE_PLATFORM_MACOS_MVK" => "macos",
+        "VK_USE_PLATFORM_IOS_MVK" => "ios",
+        "VK_USE_PLATFORM_XLIB_KHR"
+        | "VK_USE_PLATFORM_XLIB_XRANDR_EXT"
+        | "VK_USE_PLATFORM_XCB_KHR"
+        | "VK_USE_PLATFORM_WAYLAND_KHR" => "linux",
+        // Unknown or always-on guards (e.g. `VK_USE_PLATFORM_FUCHSIA`, `VK_USE_PLATFORM_GGP`,
+        // `VK_USE_PLATFORM_VI_NN`, display-server guards we don't special-case) are left
+        // ungated, since a wrong `cfg` would be worse than none.
+        _ => return quote! {},
+    };
+
+    quote! { #[cfg(target_os = #target_os)] }
+}
+
+// Like `cfg_for_platform_guard`, but for an item that's reachable through several
+// `provided_by` extensions/features at once (as `get_types`'s entries are): the item is only
+// gated if every provider that's an extension agrees on the same platform. A provider that's a
+// core feature, or an extension with no `platform` attribute, means the item isn't actually
+// platform-exclusive, so it's left ungated rather than guessing.
+pub(crate) fn cfg_for_providers(
+    providers: &[&str],
+    extensions: &IndexMap<&str, &Extension>,
+    platforms: &HashMap<&str, &str>,
+) -> proc_macro2::TokenStream {
+    let guards: Vec<Option<&str>> = providers
+        .iter()
+        .map(|provider| {
+            extensions
+                .get(provider)
+                .and_then(|ext| ext.platform.as_deref())
+                .and_then(|platform| platforms.get(platform).copied())
+        })
+        .collect();
+
+    match guards.split_first() {
+        Some((&Some(first), rest)) if rest.iter().all(|g| *g == Some(first)) => {
+            cfg_for_platform_guard(first)
+        }
+        _ => quote! {},
+    }
+}
+
 fn get_header_version(registry: &Registry) -> u16 {
     registry.0.iter()
         .find_map(|child| -> Option<u16> {
@@ -0,0 +1,33 @@
+// Copyright (c) 2021 The Vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use crate::{cfg_for_providers, resolve_video_pointee};
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::quote;
+use std::collections::{HashMap, HashSet};
+use vk_parse::{Extension, Type};
+
+pub fn write(
+    types: &HashMap<&str, (&Type, Vec<&str>)>,
+    extensions: &IndexMap<&str, &Extension>,
+    platforms: &HashMap<&str, &str>,
+    video_types: &HashSet<&str>,
+) -> TokenStream {
+    let video_aliases = types
+        .iter()
+        .filter(|(name, _)| name.starts_with("StdVideo"))
+        .map(|(&name, (_, providers))| {
+            let cfg = cfg_for_providers(providers, extensions, platforms);
+            let alias = resolve_video_pointee(name, video_types);
+            quote! { #cfg #alias }
+        });
+
+    quote! { #(#video_aliases)* }
+}
@@ -0,0 +1,84 @@
+// Copyright (c) 2021 The Vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use indexmap::IndexMap;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use vk_parse::Feature;
+
+// Mirrors the bit layout that `vk.xml` encodes in `VK_MAKE_API_VERSION` and
+// its accessors: `variant << 29 | major << 22 | minor << 12 | patch`.
+pub fn write(features: &IndexMap<&str, &Feature>) -> TokenStream {
+    let core_versions = core_version_constants(features);
+    let header_version_complete = header_version_complete(features);
+
+    quote! {
+        /// Builds an API version number from its variant, major, minor and patch components,
+        /// in the same way that the `VK_MAKE_API_VERSION` macro does.
+        pub const fn make_api_version(variant: u32, major: u32, minor: u32, patch: u32) -> u32 {
+            (variant << 29) | (major << 22) | (minor << 12) | patch
+        }
+
+        /// Extracts the variant component from an API version number.
+        pub const fn version_variant(version: u32) -> u32 {
+            version >> 29
+        }
+
+        /// Extracts the major component from an API version number.
+        pub const fn version_major(version: u32) -> u32 {
+            (version >> 22) & 0x7f
+        }
+
+        /// Extracts the minor component from an API version number.
+        pub const fn version_minor(version: u32) -> u32 {
+            (version >> 12) & 0x3ff
+        }
+
+        /// Extracts the patch component from an API version number.
+        pub const fn version_patch(version: u32) -> u32 {
+            version & 0xfff
+        }
+
+        #(#core_versions)*
+
+        #header_version_complete
+    }
+}
+
+fn core_version_constants(features: &IndexMap<&str, &Feature>) -> Vec<TokenStream> {
+    features
+        .values()
+        .filter_map(|feature| {
+            let (major, minor) = feature.number.split_once('.')?;
+            let major: u32 = major.parse().ok()?;
+            let minor: u32 = minor.parse().ok()?;
+            let ident = format_ident!("API_VERSION_{}_{}", major, minor);
+
+            Some(quote! {
+                pub const #ident: u32 = make_api_version(0, #major, #minor, 0);
+            })
+        })
+        .collect()
+}
+
+fn header_version_complete(features: &IndexMap<&str, &Feature>) -> TokenStream {
+    let (major, minor) = features
+        .values()
+        .filter_map(|feature| feature.number.split_once('.'))
+        .filter_map(|(major, minor)| Some((major.parse::<u32>().ok()?, minor.parse::<u32>().ok()?)))
+        .max()
+        .unwrap_or((1, 0));
+
+    quote! {
+        /// The complete Vulkan API version supported by this version of vulkano, combining the
+        /// highest core version with the header's patch version.
+        pub const HEADER_VERSION_COMPLETE: u32 =
+            make_api_version(0, #major, #minor, HEADER_VERSION as u32);
+    }
+}
@@ -0,0 +1,370 @@
+// Copyright (c) 2021 The Vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// https://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or https://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use roxmltree::{Document, Node};
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+};
+
+// The `VK_KHR_video_*` extensions reference `StdVideo*` types through opaque
+// pointers. Those types aren't part of `vk.xml`; they're described by a
+// separate registry (`video.xml`) that mirrors the `vk_video/*.h` headers.
+// This pass lowers that registry into a `video` module, and `mod.rs` feeds
+// the name set below to the `properties` pass so it resolves the real types
+// instead of falling back to `c_void`.
+pub struct VideoRegistry<'a> {
+    pub structs: HashMap<&'a str, VideoStruct<'a>>,
+    pub enums: HashMap<&'a str, VideoEnum<'a>>,
+}
+
+pub fn parse<P: AsRef<Path> + ?Sized>(path: &P) -> VideoRegistry<'static> {
+    let registry = get_video_registry(path);
+    VideoRegistry {
+        structs: get_structs(&registry),
+        enums: get_enums(&registry),
+    }
+}
+
+pub fn type_names<'a>(registry: &VideoRegistry<'a>) -> HashSet<&'a str> {
+    registry
+        .structs
+        .keys()
+        .copied()
+        .chain(registry.enums.keys().copied())
+        .collect()
+}
+
+pub fn write<W: std::io::Write>(writer: &mut W, registry: &VideoRegistry<'_>) {
+    let output = structs_output(&registry.structs, &registry.enums)
+        .into_iter()
+        .chain(enums_output(&registry.enums));
+
+    write!(
+        writer,
+        "\
+        pub mod video {{\n\
+        {}\n\
+        }}\n\
+        ",
+        quote! { #(#output)* }
+    )
+    .unwrap();
+}
+
+fn get_video_registry<P: AsRef<Path> + ?Sized>(path: &P) -> Document<'static> {
+    let contents = std::fs::read_to_string(path).unwrap();
+    // `video.xml` is parsed once per generator run and leaked for the
+    // lifetime of the process; the generator is short-lived so this is fine.
+    let contents: &'static str = Box::leak(contents.into_boxed_str());
+    Document::parse(contents).unwrap()
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ArrayLen<'a> {
+    Literal(u64),
+    /// Named by a `<enum>` reference into one of the registry's `<enums>` blocks (its
+    /// value is looked up there, so the generated array length is still a literal).
+    Named(&'a str),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct VideoMember<'a> {
+    name: &'a str,
+    ty: &'a str,
+    pointer: bool,
+    /// Width of a C bitfield (`: N` after the member name), if this member is one.
+    bitfield: Option<u32>,
+    array_len: Option<ArrayLen<'a>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct VideoStruct<'a> {
+    name: &'a str,
+    members: Vec<VideoMember<'a>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct VideoEnum<'a> {
+    name: &'a str,
+    variants: Vec<(&'a str, i64)>,
+}
+
+fn get_structs<'a>(registry: &'a Document<'a>) -> HashMap<&'a str, VideoStruct<'a>> {
+    registry
+        .root_element()
+        .children()
+        .filter(|node| node.has_tag_name("types"))
+        .flat_map(|types| types.children())
+        .filter(|node| node.has_tag_name("type") && node.attribute("category") == Some("struct"))
+        .filter_map(|node| {
+            let name = node.attribute("name")?;
+            let members = node
+                .children()
+                .filter(|member| member.has_tag_name("member"))
+                .filter_map(parse_member)
+                .collect();
+
+            Some((name, VideoStruct { name, members }))
+        })
+        .collect()
+}
+
+// Walks a `<member>` node's children in document order to recover everything the previous,
+// text()-only parse threw away: a leading `*` marks a pointer, a trailing `: N` is a bitfield
+// width, and a trailing `[N]` or `[<enum>NAME</enum>]` is an array length. Getting these wrong
+// silently produces a `#[repr(C)]` struct with the wrong size, so this has to track the raw
+// member syntax rather than just the `<type>`/`<name>` text nodes.
+fn parse_member<'a>(member: Node<'a, 'a>) -> Option<VideoMember<'a>> {
+    let mut ty = None;
+    let mut name = None;
+    let mut pointer = false;
+    let mut bitfield = None;
+    let mut array_len = None;
+    let mut seen_name = false;
+    let mut expecting_array_enum = false;
+
+    for child in member.children() {
+        if child.is_text() {
+            let text = child.text().unwrap_or("").trim();
+
+            if !seen_name {
+                if text.contains('*') {
+                    pointer = true;
+                }
+            } else if let Some(width) = text.strip_prefix(':').and_then(|w| w.trim().parse().ok())
+            {
+                bitfield = Some(width);
+            } else if let Some(rest) = text.strip_prefix('[') {
+                expecting_array_enum = true;
+                if let Some(literal) = rest.split(']').next().and_then(|n| n.parse().ok()) {
+                    array_len = Some(ArrayLen::Literal(literal));
+                    expecting_array_enum = false;
+                }
+            }
+        } else if child.has_tag_name("type") {
+            ty = child.text();
+        } else if child.has_tag_name("name") {
+            name = child.text();
+            seen_name = true;
+        } else if child.has_tag_name("enum") && expecting_array_enum {
+            array_len = child.text().map(ArrayLen::Named);
+            expecting_array_enum = false;
+        }
+    }
+
+    Some(VideoMember {
+        name: name?,
+        ty: ty?,
+        pointer,
+        bitfield,
+        array_len,
+    })
+}
+
+fn get_enums<'a>(registry: &'a Document<'a>) -> HashMap<&'a str, VideoEnum<'a>> {
+    registry
+        .root_element()
+        .children()
+        .filter(|node| node.has_tag_name("enums"))
+        .filter_map(|node| {
+            let name = node.attribute("name")?;
+            let variants = node
+                .children()
+                .filter(|variant| variant.has_tag_name("enum"))
+                .filter_map(|variant| {
+                    let variant_name = variant.attribute("name")?;
+                    let value = variant.attribute("value")?.parse().ok()?;
+                    Some((variant_name, value))
+                })
+                .collect();
+
+            Some((name, VideoEnum { name, variants }))
+        })
+        .collect()
+}
+
+fn resolve_array_len(len: ArrayLen<'_>, enums: &HashMap<&str, VideoEnum<'_>>) -> u64 {
+    match len {
+        ArrayLen::Literal(n) => n,
+        ArrayLen::Named(name) => enums
+            .values()
+            .flat_map(|e| e.variants.iter())
+            .find(|(variant_name, _)| *variant_name == name)
+            .map(|(_, value)| *value as u64)
+            .unwrap_or_else(|| panic!("video.xml: unknown array length constant `{name}`")),
+    }
+}
+
+fn structs_output<'a>(
+    structs: &HashMap<&'a str, VideoStruct<'a>>,
+    enums: &HashMap<&'a str, VideoEnum<'a>>,
+) -> Vec<TokenStream> {
+    structs
+        .values()
+        .map(|s| {
+            let struct_ident = format_ident!("{}", s.name);
+            let fields = member_fields(&s.members, structs, enums);
+
+            quote! {
+                #[repr(C)]
+                #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+                pub struct #struct_ident {
+                    #(#fields,)*
+                }
+            }
+        })
+        .collect()
+}
+
+// Emits one field per member, except that a run of consecutive bitfields sharing the same
+// declared type is merged into a single storage field sized for the whole run. Rust has no
+// `#[repr(C)]` bitfield syntax, so splitting each bit out as its own field (as the previous
+// version effectively did by ignoring `bitfield` entirely) would both be wrong and balloon the
+// struct's size; merging keeps the overall layout correct even without per-bit accessors.
+fn member_fields<'a>(
+    members: &[VideoMember<'a>],
+    structs: &HashMap<&'a str, VideoStruct<'a>>,
+    enums: &HashMap<&'a str, VideoEnum<'a>>,
+) -> Vec<TokenStream> {
+    let mut fields = Vec::new();
+    let mut i = 0;
+
+    while i < members.len() {
+        let member = &members[i];
+
+        if let Some(first_width) = member.bitfield {
+            let mut j = i + 1;
+            let mut total_width = first_width;
+            while j < members.len()
+                && members[j].bitfield.is_some()
+                && members[j].ty == member.ty
+            {
+                total_width += members[j].bitfield.unwrap();
+                j += 1;
+            }
+
+            let storage_width = c_type_bit_width(member.ty).unwrap_or_else(|| {
+                panic!(
+                    "video.xml: bitfield member `{}` has unrecognized storage type `{}`",
+                    member.name, member.ty
+                )
+            });
+            assert!(
+                total_width <= storage_width,
+                "video.xml: bitfield run starting at `{}` sums to {total_width} bits, which \
+                 doesn't fit in its {storage_width}-bit storage type `{}`",
+                member.name,
+                member.ty,
+            );
+
+            let field_ident = format_ident!("{}_bitfield", member.name);
+            let ty_ident = format_ident!("{}", c_type_to_rust(member.ty));
+            fields.push(quote! { pub #field_ident: #ty_ident });
+            i = j;
+            continue;
+        }
+
+        let member_ident = format_ident!("{}", member.name);
+        let base_ty = resolved_type_ident(member.ty, structs, enums);
+
+        let ty_tokens = if member.pointer {
+            quote! { *const #base_ty }
+        } else if let Some(len) = member.array_len {
+            let len = resolve_array_len(len, enums);
+            quote! { [#base_ty; #len as usize] }
+        } else {
+            quote! { #base_ty }
+        };
+
+        fields.push(quote! { pub #member_ident: #ty_tokens });
+        i += 1;
+    }
+
+    fields
+}
+
+fn resolved_type_ident<'a>(
+    ty: &'a str,
+    structs: &HashMap<&'a str, VideoStruct<'a>>,
+    enums: &HashMap<&'a str, VideoEnum<'a>>,
+) -> proc_macro2::Ident {
+    if enums.contains_key(ty) || structs.contains_key(ty) {
+        format_ident!("{}", ty)
+    } else {
+        format_ident!("{}", c_type_to_rust(ty))
+    }
+}
+
+fn enums_output<'a>(enums: &HashMap<&'a str, VideoEnum<'a>>) -> Vec<TokenStream> {
+    enums
+        .values()
+        .map(|e| {
+            let enum_ident = format_ident!("{}", e.name);
+            let variants = e.variants.iter().map(|(variant_name, value)| {
+                let variant_ident = format_ident!("{}", variant_name);
+                let value = *value as i32;
+                quote! { #variant_ident = #value }
+            });
+
+            // Every generated enum needs a `Default` impl, since it may be embedded by value
+            // in a `#[derive(Default)]` struct above; the registry doesn't name a default
+            // variant, so the first-declared one is used, matching its `= 0` convention.
+            let default_impl = e.variants.first().map(|(first_variant, _)| {
+                let first_ident = format_ident!("{}", first_variant);
+                quote! {
+                    impl Default for #enum_ident {
+                        fn default() -> Self {
+                            Self::#first_ident
+                        }
+                    }
+                }
+            });
+
+            quote! {
+                #[repr(i32)]
+                #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+                pub enum #enum_ident {
+                    #(#variants,)*
+                }
+
+                #default_impl
+            }
+        })
+        .collect()
+}
+
+// Bit width of a bitfield's declared storage type, used to make sure a merged run of
+// consecutive bitfields (see `member_fields` above) actually fits in it.
+fn c_type_bit_width(ty: &str) -> Option<u32> {
+    match ty {
+        "uint8_t" | "int8_t" => Some(8),
+        "uint16_t" | "int16_t" => Some(16),
+        "uint32_t" | "int32_t" | "float" => Some(32),
+        "uint64_t" | "int64_t" => Some(64),
+        _ => None,
+    }
+}
+
+fn c_type_to_rust(ty: &str) -> String {
+    match ty {
+        "uint8_t" => "u8".to_owned(),
+        "uint16_t" => "u16".to_owned(),
+        "uint32_t" => "u32".to_owned(),
+        "uint64_t" => "u64".to_owned(),
+        "int8_t" => "i8".to_owned(),
+        "int16_t" => "i16".to_owned(),
+        "int32_t" => "i32".to_owned(),
+        "int64_t" => "i64".to_owned(),
+        "float" => "f32".to_owned(),
+        other => other.to_owned(),
+    }
+}